@@ -5,6 +5,7 @@ import (
 	"context"
 	"crypto/ed25519"
 	"crypto/sha256"
+	"encoding/binary"
 	"encoding/json"
 	"fmt"
 	"log"
@@ -29,6 +30,12 @@ func generateDiscriminator(namespace, name string) []byte {
 	return hash[:8]
 }
 
+// appendBorshString appends a Borsh string: a u32 little-endian length followed by the bytes
+func appendBorshString(data []byte, s string) []byte {
+	data = binary.LittleEndian.AppendUint32(data, uint32(len(s)))
+	return append(data, s...)
+}
+
 // Campaign represents the campaign account structure
 type Campaign struct {
 	Admin         solana.PublicKey `json:"admin"`
@@ -268,6 +275,74 @@ func (app *SolanaDApp) CreateCampaignPDA(campaignName string) (solana.PublicKey,
 	return solana.FindProgramAddress(seeds, app.programID)
 }
 
+// GlobalStatsPDA returns the program-wide stats account that create and donate update
+func (app *SolanaDApp) GlobalStatsPDA() (solana.PublicKey, error) {
+	pda, _, err := solana.FindProgramAddress([][]byte{[]byte("GLOBAL_STATS")}, app.programID)
+	return pda, err
+}
+
+// DonationRecordPDA returns the account tracking this wallet's donations to a campaign
+func (app *SolanaDApp) DonationRecordPDA(campaign solana.PublicKey) (solana.PublicKey, error) {
+	seeds := [][]byte{
+		[]byte("DONATION"),
+		campaign.Bytes(),
+		app.wallet.PublicKey.Bytes(),
+	}
+	pda, _, err := solana.FindProgramAddress(seeds, app.programID)
+	return pda, err
+}
+
+// PlatformConfigPDA returns the account holding the platform fee terms copied into new campaigns
+func (app *SolanaDApp) PlatformConfigPDA() (solana.PublicKey, error) {
+	pda, _, err := solana.FindProgramAddress([][]byte{[]byte("PLATFORM_CONFIG")}, app.programID)
+	return pda, err
+}
+
+// CampaignFeeCollector reads the fee_collector a withdrawal must pay the platform fee to.
+// The fields before it include strings, so the account data is walked field by field.
+func (app *SolanaDApp) CampaignFeeCollector(campaign solana.PublicKey) (solana.PublicKey, error) {
+	accountInfo, err := app.client.GetAccountInfo(context.Background(), campaign)
+	if err != nil {
+		return solana.PublicKey{}, fmt.Errorf("failed to fetch campaign: %w", err)
+	}
+	data := accountInfo.Value.Data.GetBinary()
+
+	offset := 0
+	truncated := false
+	skip := func(n int) {
+		if truncated || offset+n > len(data) {
+			truncated = true
+			return
+		}
+		offset += n
+	}
+	skipString := func() {
+		skip(4)
+		if !truncated {
+			skip(int(binary.LittleEndian.Uint32(data[offset-4 : offset])))
+		}
+	}
+
+	skip(8)      // discriminator
+	skip(32)     // admin
+	skipString() // name
+	skipString() // description
+	skip(17)     // amount_donated, bump, currency_label
+	skipString() // thank_you_message
+	skip(66)     // goal, end_time, metadata_locked, creator, status, min_donation, donation_count
+	skip(1)      // spl_mint option tag
+	if !truncated && data[offset-1] == 1 {
+		skip(32) // spl_mint
+	}
+	skip(11) // spl_amount_donated, finalized, fee_bps
+	skip(32) // fee_collector
+	if truncated {
+		return solana.PublicKey{}, fmt.Errorf("campaign account data is too short")
+	}
+
+	return solana.PublicKeyFromBytes(data[offset-32 : offset]), nil
+}
+
 // CheckExistingCampaign checks if a properly initialized campaign already exists for this wallet and campaign name
 func (app *SolanaDApp) CheckExistingCampaign(campaignName string) (*solana.PublicKey, error) {
 	campaignPDA, _, err := app.CreateCampaignPDA(campaignName)
@@ -355,7 +430,7 @@ func (app *SolanaDApp) CheckCampaignStatus(campaignName string) error {
 }
 
 // CreateCampaign creates a new fundraising campaign
-func (app *SolanaDApp) CreateCampaign(name, description string) error {
+func (app *SolanaDApp) CreateCampaign(name, description string, goal uint64, durationSeconds int64) error {
 	// First, check if a campaign already exists
 	existingCampaign, err := app.CheckExistingCampaign(name)
 	if err != nil {
@@ -378,27 +453,32 @@ func (app *SolanaDApp) CreateCampaign(name, description string) error {
 		return fmt.Errorf("failed to create campaign PDA: %w", err)
 	}
 
+	globalStats, err := app.GlobalStatsPDA()
+	if err != nil {
+		return fmt.Errorf("failed to derive global stats PDA: %w", err)
+	}
+
+	platformConfig, err := app.PlatformConfigPDA()
+	if err != nil {
+		return fmt.Errorf("failed to derive platform config PDA: %w", err)
+	}
+
 	// Build the instruction data for Anchor program
 	// Generate the correct discriminator for the "create" instruction
 	instructionData := generateDiscriminator("global", "create")
 
-	// Serialize name length and name (u32 + string)
-	nameLen := uint32(len(name))
-	nameLenBytes := make([]byte, 4)
-	for i := 0; i < 4; i++ {
-		nameLenBytes[i] = byte(nameLen >> (i * 8))
-	}
-	instructionData = append(instructionData, nameLenBytes...)
-	instructionData = append(instructionData, []byte(name)...)
-
-	// Serialize description length and description (u32 + string)
-	descLen := uint32(len(description))
-	descLenBytes := make([]byte, 4)
-	for i := 0; i < 4; i++ {
-		descLenBytes[i] = byte(descLen >> (i * 8))
-	}
-	instructionData = append(instructionData, descLenBytes...)
-	instructionData = append(instructionData, []byte(description)...)
+	// Serialize CreateCampaignArgs field by field, in declaration order
+	instructionData = appendBorshString(instructionData, name)
+	instructionData = appendBorshString(instructionData, description)
+	instructionData = append(instructionData, 0)                                                 // currency_label: None, defaults to SOL
+	instructionData = appendBorshString(instructionData, "")                                     // thank_you_message
+	instructionData = binary.LittleEndian.AppendUint64(instructionData, goal)                    // goal (lamports)
+	instructionData = binary.LittleEndian.AppendUint64(instructionData, uint64(durationSeconds)) // duration_seconds (i64)
+	instructionData = binary.LittleEndian.AppendUint64(instructionData, 0)                       // min_donation
+	instructionData = binary.LittleEndian.AppendUint32(instructionData, 0)                       // milestones: empty vec
+	instructionData = append(instructionData, 0)                                                 // all_or_nothing: false
+	instructionData = appendBorshString(instructionData, "")                                     // category
+	instructionData = appendBorshString(instructionData, "")                                     // metadata_uri
 
 	instruction := &solana.GenericInstruction{
 		ProgID: app.programID,
@@ -408,6 +488,16 @@ func (app *SolanaDApp) CreateCampaign(name, description string) error {
 				IsWritable: true,
 				IsSigner:   false,
 			},
+			{
+				PublicKey:  globalStats,
+				IsWritable: true,
+				IsSigner:   false,
+			},
+			{
+				PublicKey:  platformConfig,
+				IsWritable: false,
+				IsSigner:   false,
+			},
 			{
 				PublicKey:  app.wallet.PublicKey,
 				IsWritable: true,
@@ -474,6 +564,16 @@ func (app *SolanaDApp) DonateToCampaign(campaignName, campaignAddress string, am
 
 	campaignPubkey := solana.MustPublicKeyFromBase58(campaignAddress)
 
+	globalStats, err := app.GlobalStatsPDA()
+	if err != nil {
+		return fmt.Errorf("failed to derive global stats PDA: %w", err)
+	}
+
+	donationRecord, err := app.DonationRecordPDA(campaignPubkey)
+	if err != nil {
+		return fmt.Errorf("failed to derive donation record PDA: %w", err)
+	}
+
 	// Build donate instruction with proper discriminator
 	instructionData := generateDiscriminator("global", "donate")
 	// Add name length and name (u32 + string)
@@ -499,6 +599,16 @@ func (app *SolanaDApp) DonateToCampaign(campaignName, campaignAddress string, am
 				IsWritable: true,
 				IsSigner:   false,
 			},
+			{
+				PublicKey:  globalStats,
+				IsWritable: true,
+				IsSigner:   false,
+			},
+			{
+				PublicKey:  donationRecord,
+				IsWritable: true,
+				IsSigner:   false,
+			},
 			{
 				PublicKey:  app.wallet.PublicKey,
 				IsWritable: true,
@@ -523,6 +633,11 @@ func (app *SolanaDApp) WithdrawFromCampaign(campaignName, campaignAddress string
 
 	campaignPubkey := solana.MustPublicKeyFromBase58(campaignAddress)
 
+	feeCollector, err := app.CampaignFeeCollector(campaignPubkey)
+	if err != nil {
+		return err
+	}
+
 	// Build withdraw instruction with proper discriminator
 	instructionData := generateDiscriminator("global", "withdraw")
 	// Add name length and name (u32 + string)
@@ -553,6 +668,11 @@ func (app *SolanaDApp) WithdrawFromCampaign(campaignName, campaignAddress string
 				IsWritable: true,
 				IsSigner:   true,
 			},
+			{
+				PublicKey:  feeCollector,
+				IsWritable: true,
+				IsSigner:   false,
+			},
 		},
 		DataBytes: instructionData,
 	}
@@ -663,7 +783,23 @@ func (app *SolanaDApp) Run() {
 			description, _ := reader.ReadString('\n')
 			description = strings.TrimSpace(description)
 
-			if err := app.CreateCampaign(name, description); err != nil {
+			fmt.Print("Goal (lamports): ")
+			goalStr, _ := reader.ReadString('\n')
+			goal, err := strconv.ParseUint(strings.TrimSpace(goalStr), 10, 64)
+			if err != nil {
+				fmt.Println("❌ Invalid goal. Please enter a valid number.")
+				continue
+			}
+
+			fmt.Print("Duration (seconds): ")
+			durationStr, _ := reader.ReadString('\n')
+			duration, err := strconv.ParseInt(strings.TrimSpace(durationStr), 10, 64)
+			if err != nil || duration <= 0 {
+				fmt.Println("❌ Duration must be a positive number of seconds.")
+				continue
+			}
+
+			if err := app.CreateCampaign(name, description, goal, duration); err != nil {
 				if strings.Contains(err.Error(), "insufficient") {
 					fmt.Println("❌ Insufficient SOL in your wallet. Please use option 1 to get SOL via airdrop.")
 				} else {
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crowdfunding::program::Crowdfunding;
+
+declare_id!("9y4MHDtUPhxnKr23qU3bq82NufDK15zLvWGXgqLXweK");
+
+// Minimal example of another program donating to a campaign through
+// crowdfunding's donate_by_name, which only needs the creator and name
+#[program]
+pub mod cpi_caller {
+    use super::*;
+
+    pub fn donate(ctx: Context<Donate>, creator: Pubkey, name: String, amount: u64) -> Result<()> {
+        let accounts = crowdfunding::cpi::accounts::DonateByName {
+            campaign: ctx.accounts.campaign.to_account_info(),
+            global_stats: ctx.accounts.global_stats.to_account_info(),
+            donation_record: ctx.accounts.donation_record.to_account_info(),
+            user: ctx.accounts.user.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.crowdfunding_program.to_account_info(), accounts);
+        crowdfunding::cpi::donate_by_name(cpi_ctx, creator, name, amount)
+    }
+}
+
+#[derive(Accounts)]
+pub struct Donate<'info> {
+    /// CHECK: checked by the crowdfunding program against creator and name
+    #[account(mut)]
+    pub campaign: UncheckedAccount<'info>,
+    /// CHECK: crowdfunding's GLOBAL_STATS PDA, checked by the crowdfunding program
+    #[account(mut)]
+    pub global_stats: UncheckedAccount<'info>,
+    /// CHECK: the donor's DONATION PDA, checked by the crowdfunding program
+    #[account(mut)]
+    pub donation_record: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub crowdfunding_program: Program<'info, Crowdfunding>,
+}
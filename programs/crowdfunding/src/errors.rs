@@ -7,4 +7,72 @@ pub enum CampaignError {
     Unauthorized,
     #[msg("Insufficient funds to perform this action.")]
     InsufficientFunds,
+    #[msg("Thank-you message exceeds the maximum length.")]
+    ThankYouMessageTooLong,
+    #[msg("This campaign has ended.")]
+    CampaignEnded,
+    #[msg("Campaign metadata has been locked and can no longer be changed.")]
+    MetadataLocked,
+    #[msg("The campaign reached its goal, so refunds are not available.")]
+    GoalMet,
+    #[msg("The campaign is still active, so refunds are not available yet.")]
+    CampaignActive,
+    #[msg("Withdraw the remaining donations and tokens before closing the campaign.")]
+    CampaignNotEmpty,
+    #[msg("Description does not fit in the campaign account.")]
+    DescriptionTooLong,
+    #[msg("Donation total would overflow.")]
+    AmountOverflow,
+    #[msg("Amount must be greater than zero.")]
+    InvalidAmount,
+    #[msg("Campaign name must be at most 32 bytes.")]
+    NameTooLong,
+    #[msg("Campaign name contains characters that are not allowed.")]
+    InvalidName,
+    #[msg("This campaign is not accepting donations.")]
+    CampaignNotActive,
+    #[msg("This campaign is not paused.")]
+    CampaignNotPaused,
+    #[msg("Donation is below this campaign's minimum.")]
+    BelowMinimum,
+    #[msg("This campaign does not accept donations in this token.")]
+    MintMismatch,
+    #[msg("SPL donations are already enabled for this campaign.")]
+    SplAlreadyEnabled,
+    #[msg("This campaign has been finalized and no longer accepts donations.")]
+    CampaignFinalized,
+    #[msg("Platform fee cannot exceed 10000 basis points.")]
+    FeeTooHigh,
+    #[msg("There are no funds above the rent-exempt minimum to withdraw.")]
+    NothingToWithdraw,
+    #[msg("Donations have not reached the next milestone yet.")]
+    MilestoneNotReached,
+    #[msg("Milestones must be positive, strictly increasing, at most the goal and at most 8 long.")]
+    InvalidMilestones,
+    #[msg("This all-or-nothing campaign has not reached its goal.")]
+    GoalNotReached,
+    #[msg("A campaign can have at most 4 co-admins.")]
+    TooManyCoAdmins,
+    #[msg("That key is already the admin or a co-admin.")]
+    DuplicateCoAdmin,
+    #[msg("That key is not a co-admin of this campaign.")]
+    CoAdminNotFound,
+    #[msg("Category must be at most 32 bytes.")]
+    CategoryTooLong,
+    #[msg("Metadata URI must be at most 200 bytes.")]
+    MetadataUriTooLong,
+    #[msg("Campaign duration must be positive and end within the supported time range.")]
+    InvalidDuration,
+    #[msg("The goal of an all-or-nothing campaign cannot be changed.")]
+    GoalLocked,
+    #[msg("This donation record belongs to an earlier campaign at this address.")]
+    StaleDonationRecord,
+    #[msg("Pass the campaign's token account so it can be checked for tokens.")]
+    TokenAccountRequired,
+    #[msg("The campaign missed its goal, so the remaining funds are reserved for refunds.")]
+    RefundsOpen,
+    #[msg("All-or-nothing and milestone campaigns accept SOL donations only.")]
+    SplNotSupported,
+    #[msg("This donation record can still be refunded.")]
+    DonationRecordInUse,
 }
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct DonationReceived {
+    pub campaign: Pubkey,
+    pub donor: Pubkey,
+    pub amount: u64,
+    pub total: u64,
+    pub mint: Option<Pubkey>, // None for SOL donations
+}
+
+#[event]
+pub struct CampaignCreated {
+    pub campaign: Pubkey,
+    pub admin: Pubkey,
+    pub name: String,
+    pub goal: u64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct FundsWithdrawn {
+    pub campaign: Pubkey,
+    pub admin: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub mint: Option<Pubkey>, // None for SOL withdrawals
+}
+
+#[event]
+pub struct GoalReached {
+    pub campaign: Pubkey,
+    pub total: u64,
+}
@@ -1,53 +1,687 @@
 use anchor_lang::prelude::*;
-use crate::{Campaign, CampaignError, Create, Withdraw, Donate};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, CloseAccount, TransferChecked};
+use crate::{Campaign, CampaignStatus, GlobalStats, DonationRecord, CampaignError, CampaignCreated, DonationReceived, FundsWithdrawn, GoalReached, Create, CreateCampaignArgs, Withdraw, WithdrawAll, SetThankYou, UpdateDescription, SetTarget, ExtendDeadline, FreezeMetadata, Donate, DonateByName, EnableSpl, DonateSpl, WithdrawSpl, Pause, Resume, Finalize, TransferAdmin, AddCoAdmin, RemoveCoAdmin, Close, Refund, CloseDonationRecord, InitializePlatformConfig, SetPlatformAuthority, SetPlatformFee, DEFAULT_CURRENCY_LABEL, MAX_CATEGORY_LEN, MAX_CO_ADMINS, MAX_FEE_BPS, MAX_METADATA_URI_LEN, MAX_MILESTONES, MAX_THANK_YOU_LEN};
+
+pub fn create(ctx: Context<Create>, args: CreateCampaignArgs) -> Result<()> {
+    let CreateCampaignArgs {
+        name,
+        description,
+        currency_label,
+        thank_you_message,
+        goal,
+        duration_seconds,
+        min_donation,
+        milestones,
+        all_or_nothing,
+        category,
+        metadata_uri,
+    } = args;
+
+    if thank_you_message.len() > MAX_THANK_YOU_LEN {
+        return Err(CampaignError::ThankYouMessageTooLong.into());
+    }
+
+    if duration_seconds <= 0 {
+        return Err(CampaignError::InvalidDuration.into());
+    }
+
+    // A zero first milestone would be a zero tranche that can never be withdrawn,
+    // and one above the goal might never be reached by a successful campaign
+    if milestones.len() > MAX_MILESTONES
+        || milestones.first() == Some(&0)
+        || milestones.last().is_some_and(|&last| last > goal)
+        || milestones.windows(2).any(|pair| pair[0] >= pair[1])
+    {
+        return Err(CampaignError::InvalidMilestones.into());
+    }
+
+    if category.len() > MAX_CATEGORY_LEN {
+        return Err(CampaignError::CategoryTooLong.into());
+    }
+
+    if metadata_uri.len() > MAX_METADATA_URI_LEN {
+        return Err(CampaignError::MetadataUriTooLong.into());
+    }
 
-pub fn create(ctx: Context<Create>, name: String, description: String) -> Result<()> {
     let campaign = &mut ctx.accounts.campaign;
     campaign.name = name;
     campaign.description = description;
     campaign.amount_donated = 0;
     campaign.admin = *ctx.accounts.user.key;
+    campaign.creator = *ctx.accounts.user.key;
     campaign.bump = ctx.bumps.campaign;
+    campaign.currency_label = currency_label.unwrap_or(DEFAULT_CURRENCY_LABEL);
+    campaign.thank_you_message = thank_you_message;
+    campaign.goal = goal;
+    campaign.end_time = Clock::get()?.unix_timestamp
+        .checked_add(duration_seconds)
+        .ok_or(CampaignError::InvalidDuration)?;
+    campaign.metadata_locked = false;
+    campaign.status = CampaignStatus::Active;
+    campaign.min_donation = min_donation;
+    campaign.donation_count = 0;
+    campaign.spl_mint = None;
+    campaign.spl_amount_donated = 0;
+    campaign.finalized = false;
+    // Fee terms come from the platform and are fixed for the campaign's lifetime
+    let platform_config = &ctx.accounts.platform_config;
+    campaign.fee_bps = platform_config.fee_bps;
+    campaign.fee_collector = platform_config.fee_collector;
+    campaign.milestones = milestones;
+    campaign.milestones_withdrawn = 0;
+    campaign.goal_reached = false;
+    campaign.all_or_nothing = all_or_nothing;
+    campaign.last_donation_at = 0;
+    campaign.co_admins = Vec::new();
+    campaign.category = category;
+    campaign.metadata_uri = metadata_uri;
+
+    let global_stats = &mut ctx.accounts.global_stats;
+    global_stats.total_campaigns = global_stats.total_campaigns
+        .checked_add(1)
+        .ok_or(CampaignError::AmountOverflow)?;
+    global_stats.bump = ctx.bumps.global_stats;
+    campaign.campaign_id = global_stats.total_campaigns;
+
+    emit!(CampaignCreated {
+        campaign: campaign.key(),
+        admin: campaign.admin,
+        name: campaign.name.clone(),
+        goal: campaign.goal,
+        end_time: campaign.end_time,
+    });
     Ok(())
 }
 
-pub fn withdraw(ctx: Context<Withdraw>, name: String, amount: u64) -> Result<()> {
-    let campaign = &mut ctx.accounts.campaign;
-    let user = &mut ctx.accounts.user;
-    
-    if campaign.admin != *user.key {
-        return Err(CampaignError::Unauthorized.into());
+pub fn withdraw(ctx: Context<Withdraw>, _name: String, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Err(CampaignError::InvalidAmount.into());
     }
 
-    let rent_balance = Rent::get()?.minimum_balance(campaign.to_account_info().data_len());
+    let accounts = ctx.accounts;
+    transfer_withdrawal(&mut accounts.campaign, &accounts.admin, &accounts.fee_collector, amount)
+}
+
+pub fn withdraw_all(ctx: Context<WithdrawAll>, _name: String) -> Result<()> {
+    let accounts = ctx.accounts;
+
+    let amount = match accounts.campaign.next_milestone_tranche(Clock::get()?.unix_timestamp)? {
+        Some(tranche) => tranche,
+        None => withdrawable_balance(&accounts.campaign)?,
+    };
+
+    if amount == 0 {
+        return Err(CampaignError::NothingToWithdraw.into());
+    }
+
+    transfer_withdrawal(&mut accounts.campaign, &accounts.admin, &accounts.fee_collector, amount)
+}
+
+// Lamports held above the rent-exempt minimum
+fn withdrawable_balance(campaign: &Account<Campaign>) -> Result<u64> {
+    let campaign_info = campaign.to_account_info();
+    let rent_balance = Rent::get()?.minimum_balance(campaign_info.data_len());
+    campaign_info.lamports()
+        .checked_sub(rent_balance)
+        .ok_or_else(|| CampaignError::InsufficientFunds.into())
+}
+
+fn platform_fee(campaign: &Campaign, amount: u64) -> Result<u64> {
+    u64::try_from(u128::from(amount) * u128::from(campaign.fee_bps) / u128::from(MAX_FEE_BPS))
+        .map_err(|_| CampaignError::AmountOverflow.into())
+}
+
+fn transfer_withdrawal<'info>(
+    campaign: &mut Account<'info, Campaign>,
+    admin: &Signer<'info>,
+    fee_collector: &UncheckedAccount<'info>,
+    amount: u64,
+) -> Result<()> {
+    if campaign.all_or_nothing && !campaign.goal_met() {
+        return Err(CampaignError::GoalNotReached.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Once the deadline passes below goal, what is left belongs to refunding donors
+    if now > campaign.end_time && !campaign.goal_met() {
+        return Err(CampaignError::RefundsOpen.into());
+    }
+
+    // Milestone-gated campaigns release exactly one tranche per withdrawal
+    if let Some(tranche) = campaign.next_milestone_tranche(now)? {
+        if amount != tranche {
+            return Err(CampaignError::InvalidAmount.into());
+        }
+        campaign.milestones_withdrawn += 1;
+    }
+
+    let available = withdrawable_balance(campaign)?;
     
-    if **campaign.to_account_info().lamports.borrow() - rent_balance < amount {
+    if available < amount {
         return Err(CampaignError::InsufficientFunds.into());
     }
 
-    // Manual lamport transfer from PDA to user
-    **campaign.to_account_info().try_borrow_mut_lamports()? -= amount;
-    **user.to_account_info().try_borrow_mut_lamports()? += amount;
+    let fee = platform_fee(campaign, amount)?;
+    let payout = amount
+        .checked_sub(fee)
+        .ok_or(CampaignError::InsufficientFunds)?;
+
+    // Manual lamport transfer from PDA to admin and fee collector. A System
+    // Program transfer (even via invoke_signed) can't be used here: it only
+    // debits system-owned accounts without data, and the campaign is neither.
+    let campaign_info = campaign.to_account_info();
+    let new_balance = campaign_info.lamports()
+        .checked_sub(amount)
+        .ok_or(CampaignError::InsufficientFunds)?;
+    **campaign_info.try_borrow_mut_lamports()? = new_balance;
+    **admin.to_account_info().try_borrow_mut_lamports()? += payout;
+    **fee_collector.try_borrow_mut_lamports()? += fee;
+
+    emit!(FundsWithdrawn {
+        campaign: campaign.key(),
+        admin: admin.key(),
+        amount,
+        remaining: available - amount,
+        mint: None,
+    });
+    Ok(())
+}
+
+pub fn set_thank_you(ctx: Context<SetThankYou>, _name: String, message: String) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+
+    if message.len() > MAX_THANK_YOU_LEN {
+        return Err(CampaignError::ThankYouMessageTooLong.into());
+    }
 
+    campaign.thank_you_message = message;
     Ok(())
 }
 
-pub fn donate(ctx: Context<Donate>, name: String, amount: u64) -> Result<()> {
+pub fn update_description(ctx: Context<UpdateDescription>, _name: String, description: String) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+
+    if campaign.metadata_locked {
+        return Err(CampaignError::MetadataLocked.into());
+    }
+
+    // Measure against Campaign::space so the room reserved for
+    // thank_you_message and co_admins is never spent on the description
+    let required = Campaign::space(&campaign.name, &description, &campaign.milestones, &campaign.category, &campaign.metadata_uri);
+
+    if required > campaign.to_account_info().data_len() {
+        return Err(CampaignError::DescriptionTooLong.into());
+    }
+
+    campaign.description = description;
+    Ok(())
+}
+
+pub fn set_target(ctx: Context<SetTarget>, _name: String, goal: u64) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+
+    if campaign.metadata_locked {
+        return Err(CampaignError::MetadataLocked.into());
+    }
+
+    // Lowering the goal would release all-or-nothing funds early
+    if campaign.all_or_nothing {
+        return Err(CampaignError::GoalLocked.into());
+    }
+
+    // Once the deadline passes, donors' refund eligibility is settled
+    if Clock::get()?.unix_timestamp > campaign.end_time {
+        return Err(CampaignError::CampaignEnded.into());
+    }
+
+    campaign.goal = goal;
+
+    // Keep goal_reached in step with the new goal, emitting only if it flips on
+    let reached = campaign.goal_met();
+    if reached && !campaign.goal_reached {
+        emit!(GoalReached {
+            campaign: campaign.key(),
+            total: campaign.amount_donated,
+        });
+    }
+    campaign.goal_reached = reached;
+    Ok(())
+}
+
+pub fn extend_deadline(ctx: Context<ExtendDeadline>, _name: String, additional_seconds: u32) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+
+    if campaign.metadata_locked {
+        return Err(CampaignError::MetadataLocked.into());
+    }
+
+    // Reopening an ended campaign would block refunds that are already due
+    if Clock::get()?.unix_timestamp > campaign.end_time {
+        return Err(CampaignError::CampaignEnded.into());
+    }
+
+    campaign.end_time = campaign.end_time
+        .checked_add(i64::from(additional_seconds))
+        .ok_or(CampaignError::InvalidDuration)?;
+    Ok(())
+}
+
+pub fn freeze_metadata(ctx: Context<FreezeMetadata>, _name: String) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    campaign.metadata_locked = true;
+    Ok(())
+}
+
+pub fn donate(ctx: Context<Donate>, _name: String, amount: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    accounts.global_stats.bump = ctx.bumps.global_stats;
+    accounts.donation_record.bump = ctx.bumps.donation_record;
+    transfer_donation(&mut accounts.campaign, &mut accounts.global_stats, &mut accounts.donation_record, &accounts.user, &accounts.system_program, amount)
+}
+
+pub fn donate_by_name(ctx: Context<DonateByName>, _creator: Pubkey, _name: String, amount: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    accounts.global_stats.bump = ctx.bumps.global_stats;
+    accounts.donation_record.bump = ctx.bumps.donation_record;
+    transfer_donation(&mut accounts.campaign, &mut accounts.global_stats, &mut accounts.donation_record, &accounts.user, &accounts.system_program, amount)
+}
+
+fn transfer_donation<'info>(
+    campaign: &mut Account<'info, Campaign>,
+    global_stats: &mut Account<'info, GlobalStats>,
+    donation_record: &mut Account<'info, DonationRecord>,
+    user: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Err(CampaignError::InvalidAmount.into());
+    }
+
+    if amount < campaign.min_donation {
+        return Err(CampaignError::BelowMinimum.into());
+    }
+
+    if campaign.finalized {
+        return Err(CampaignError::CampaignFinalized.into());
+    }
+
+    if campaign.status != CampaignStatus::Active {
+        return Err(CampaignError::CampaignNotActive.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    if now > campaign.end_time {
+        return Err(CampaignError::CampaignEnded.into());
+    }
+
     let ix = anchor_lang::solana_program::system_instruction::transfer(
-        &ctx.accounts.user.key(),
-        &ctx.accounts.campaign.key(),
+        &user.key(),
+        &campaign.key(),
         amount,
     );
     
     anchor_lang::solana_program::program::invoke(
         &ix,
         &[
-            ctx.accounts.user.to_account_info(),
-            ctx.accounts.campaign.to_account_info(),
-            ctx.accounts.system_program.to_account_info()
+            user.to_account_info(),
+            campaign.to_account_info(),
+            system_program.to_account_info()
         ]
     )?;
     
-    (&mut ctx.accounts.campaign).amount_donated += amount;
+    campaign.amount_donated = add_donation(campaign.amount_donated, amount)?;
+    campaign.donation_count = campaign.donation_count
+        .checked_add(1)
+        .ok_or(CampaignError::AmountOverflow)?;
+    campaign.last_donation_at = now;
+    global_stats.total_raised = add_donation(global_stats.total_raised, amount)?;
+
+    // close frees the campaign address for re-use, so a record may still
+    // carry the amount of an earlier campaign there; that amount is not owed
+    if donation_record.campaign_id != campaign.campaign_id {
+        donation_record.campaign_id = campaign.campaign_id;
+        donation_record.amount = 0;
+    }
+
+    donation_record.donor = user.key();
+    donation_record.campaign = campaign.key();
+    donation_record.amount = add_donation(donation_record.amount, amount)?;
+
+    emit!(DonationReceived {
+        campaign: campaign.key(),
+        donor: user.key(),
+        amount,
+        total: campaign.amount_donated,
+        mint: None,
+    });
+
+    // Latch the flag so only the crossing donation emits GoalReached
+    if !campaign.goal_reached && campaign.goal_met() {
+        campaign.goal_reached = true;
+        emit!(GoalReached {
+            campaign: campaign.key(),
+            total: campaign.amount_donated,
+        });
+    }
+    Ok(())
+}
+
+// Adds a donation to a running total, failing instead of wrapping
+fn add_donation(total: u64, amount: u64) -> Result<u64> {
+    total
+        .checked_add(amount)
+        .ok_or_else(|| CampaignError::AmountOverflow.into())
+}
+
+pub fn enable_spl(ctx: Context<EnableSpl>, _name: String) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+
+    if campaign.spl_mint.is_some() {
+        return Err(CampaignError::SplAlreadyEnabled.into());
+    }
+
+    if !campaign.accepts_spl() {
+        return Err(CampaignError::SplNotSupported.into());
+    }
+
+    campaign.spl_mint = Some(ctx.accounts.mint.key());
     Ok(())
 }
+
+pub fn donate_spl(ctx: Context<DonateSpl>, _name: String, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Err(CampaignError::InvalidAmount.into());
+    }
+
+    let campaign = &mut ctx.accounts.campaign;
+
+    if !campaign.accepts_spl() {
+        return Err(CampaignError::SplNotSupported.into());
+    }
+
+    if campaign.finalized {
+        return Err(CampaignError::CampaignFinalized.into());
+    }
+
+    if campaign.status != CampaignStatus::Active {
+        return Err(CampaignError::CampaignNotActive.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    if now > campaign.end_time {
+        return Err(CampaignError::CampaignEnded.into());
+    }
+
+    token::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.donor_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.campaign_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    campaign.spl_amount_donated = add_donation(campaign.spl_amount_donated, amount)?;
+    campaign.donation_count = campaign.donation_count
+        .checked_add(1)
+        .ok_or(CampaignError::AmountOverflow)?;
+    campaign.last_donation_at = now;
+
+    emit!(DonationReceived {
+        campaign: campaign.key(),
+        donor: ctx.accounts.user.key(),
+        amount,
+        total: campaign.spl_amount_donated,
+        mint: campaign.spl_mint,
+    });
+    Ok(())
+}
+
+pub fn withdraw_spl(ctx: Context<WithdrawSpl>, name: String, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Err(CampaignError::InvalidAmount.into());
+    }
+
+    let campaign = &ctx.accounts.campaign;
+    let balance = ctx.accounts.campaign_token_account.amount;
+
+    if balance < amount {
+        return Err(CampaignError::InsufficientFunds.into());
+    }
+
+    let fee = platform_fee(campaign, amount)?;
+    let payout = amount
+        .checked_sub(fee)
+        .ok_or(CampaignError::InsufficientFunds)?;
+
+    let bump = [campaign.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref(), &bump]];
+    let accounts = &ctx.accounts;
+    for (to, value) in [(&accounts.admin_token_account, payout), (&accounts.fee_collector_token_account, fee)] {
+        if value == 0 {
+            continue;
+        }
+        token::transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.campaign_token_account.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: accounts.campaign.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            value,
+            accounts.mint.decimals,
+        )?;
+    }
+
+    emit!(FundsWithdrawn {
+        campaign: accounts.campaign.key(),
+        admin: accounts.admin.key(),
+        amount,
+        remaining: balance - amount,
+        mint: accounts.campaign.spl_mint,
+    });
+    Ok(())
+}
+
+pub fn pause(ctx: Context<Pause>, _name: String) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+
+    if campaign.status != CampaignStatus::Active {
+        return Err(CampaignError::CampaignNotActive.into());
+    }
+
+    campaign.status = CampaignStatus::Paused;
+    Ok(())
+}
+
+pub fn resume(ctx: Context<Resume>, _name: String) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+
+    if campaign.status != CampaignStatus::Paused {
+        return Err(CampaignError::CampaignNotPaused.into());
+    }
+
+    campaign.status = CampaignStatus::Active;
+    Ok(())
+}
+
+pub fn finalize(ctx: Context<Finalize>, _name: String) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    campaign.finalized = true;
+    Ok(())
+}
+
+pub fn transfer_admin(ctx: Context<TransferAdmin>, _name: String, new_admin: Pubkey) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    campaign.admin = new_admin;
+    Ok(())
+}
+
+pub fn add_co_admin(ctx: Context<AddCoAdmin>, _name: String, co_admin: Pubkey) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+
+    if campaign.can_withdraw(&co_admin) {
+        return Err(CampaignError::DuplicateCoAdmin.into());
+    }
+
+    if campaign.co_admins.len() >= MAX_CO_ADMINS {
+        return Err(CampaignError::TooManyCoAdmins.into());
+    }
+
+    campaign.co_admins.push(co_admin);
+    Ok(())
+}
+
+pub fn remove_co_admin(ctx: Context<RemoveCoAdmin>, _name: String, co_admin: Pubkey) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+
+    let Some(index) = campaign.co_admins.iter().position(|key| *key == co_admin) else {
+        return Err(CampaignError::CoAdminNotFound.into());
+    };
+
+    campaign.co_admins.remove(index);
+    Ok(())
+}
+
+pub fn close(ctx: Context<Close>, name: String) -> Result<()> {
+    let campaign = &ctx.accounts.campaign;
+
+    let rent_balance = Rent::get()?.minimum_balance(campaign.to_account_info().data_len());
+
+    if campaign.amount_donated > 0 && **campaign.to_account_info().lamports.borrow() > rent_balance {
+        return Err(CampaignError::CampaignNotEmpty.into());
+    }
+
+    // Closing would leave tokens in an account only the campaign PDA can sign for
+    if let Some(mint) = campaign.spl_mint {
+        let token_account = ctx.accounts.campaign_token_account.as_ref()
+            .filter(|account| account.key() == get_associated_token_address(&campaign.key(), &mint))
+            .ok_or(CampaignError::TokenAccountRequired)?;
+
+        if token_account.amount > 0 {
+            return Err(CampaignError::CampaignNotEmpty.into());
+        }
+
+        // Return the token account's rent too, or it would be stranded
+        let bump = [campaign.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref(), &bump]];
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: token_account.to_account_info(),
+                destination: ctx.accounts.admin.to_account_info(),
+                authority: campaign.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    Ok(())
+}
+
+pub fn refund(ctx: Context<Refund>, _name: String) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    let donation_record = &ctx.accounts.donation_record;
+    let donor = &mut ctx.accounts.donor;
+
+    if Clock::get()?.unix_timestamp <= campaign.end_time {
+        return Err(CampaignError::CampaignActive.into());
+    }
+
+    if campaign.goal_met() {
+        return Err(CampaignError::GoalMet.into());
+    }
+
+    let campaign_info = campaign.to_account_info();
+    let rent_balance = Rent::get()?.minimum_balance(campaign_info.data_len());
+    let available = campaign_info.lamports()
+        .checked_sub(rent_balance)
+        .ok_or(CampaignError::InsufficientFunds)?;
+
+    // Funds the admin withdrew before the deadline are gone, so the last
+    // donors to claim get whatever is left rather than nothing
+    let amount = donation_record.amount.min(available);
+    if amount == 0 {
+        return Err(CampaignError::InsufficientFunds.into());
+    }
+
+    // Manual lamport transfer from PDA back to the donor
+    let new_balance = campaign_info.lamports()
+        .checked_sub(amount)
+        .ok_or(CampaignError::InsufficientFunds)?;
+    **campaign_info.try_borrow_mut_lamports()? = new_balance;
+    **donor.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    campaign.amount_donated = campaign.amount_donated
+        .checked_sub(amount)
+        .ok_or(CampaignError::InsufficientFunds)?;
+    Ok(())
+}
+
+pub fn close_donation_record(ctx: Context<CloseDonationRecord>) -> Result<()> {
+    let campaign_info = &ctx.accounts.campaign;
+
+    // A closed or re-created campaign owes nothing on this record. A live one
+    // may still refund it unless it ended at its goal.
+    if campaign_info.owner == &crate::ID {
+        let campaign = Campaign::try_deserialize(&mut &campaign_info.try_borrow_data()?[..])?;
+        if campaign.campaign_id == ctx.accounts.donation_record.campaign_id
+            && (Clock::get()?.unix_timestamp <= campaign.end_time || !campaign.goal_met())
+        {
+            return Err(CampaignError::DonationRecordInUse.into());
+        }
+    }
+    Ok(())
+}
+
+pub fn initialize_platform_config(ctx: Context<InitializePlatformConfig>) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.authority = *ctx.accounts.authority.key;
+    platform_config.bump = ctx.bumps.platform_config;
+    platform_config.fee_bps = 0;
+    platform_config.fee_collector = *ctx.accounts.authority.key;
+    Ok(())
+}
+
+pub fn set_platform_authority(ctx: Context<SetPlatformAuthority>, new_authority: Pubkey) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.authority = new_authority;
+    Ok(())
+}
+
+pub fn set_platform_fee(ctx: Context<SetPlatformFee>, fee_bps: u16, fee_collector: Pubkey) -> Result<()> {
+    if fee_bps > MAX_FEE_BPS {
+        return Err(CampaignError::FeeTooHigh.into());
+    }
+
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.fee_bps = fee_bps;
+    platform_config.fee_collector = fee_collector;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_donation_rejects_overflow() {
+        assert_eq!(add_donation(u64::MAX - 1, 1).unwrap(), u64::MAX);
+        assert_eq!(
+            add_donation(u64::MAX - 1, 2).unwrap_err(),
+            CampaignError::AmountOverflow.into()
+        );
+    }
+}
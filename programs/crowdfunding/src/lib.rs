@@ -3,10 +3,12 @@ use anchor_lang::prelude::*;
 pub mod instructions;
 pub mod state;
 pub mod errors;
+pub mod events;
 
 use instructions::*;
 use state::*;
 use errors::*;
+use events::*;
 
 declare_id!("3r5NUnG85XtVExb1234ZYYyUazjchqjfYknnQATyCDzp");
 
@@ -14,15 +16,123 @@ declare_id!("3r5NUnG85XtVExb1234ZYYyUazjchqjfYknnQATyCDzp");
 pub mod crowdfunding {
     use super::*;
 
-    pub fn create(ctx: Context<Create>, name: String, description: String) -> Result<()> {
-        instructions::create(ctx, name, description)
+    pub fn create(ctx: Context<Create>, args: CreateCampaignArgs) -> Result<()> {
+        instructions::create(ctx, args)
     }
 
     pub fn withdraw(ctx: Context<Withdraw>, name: String, amount: u64) -> Result<()> {
         instructions::withdraw(ctx, name, amount)
     }
 
+    pub fn withdraw_all(ctx: Context<WithdrawAll>, name: String) -> Result<()> {
+        instructions::withdraw_all(ctx, name)
+    }
+
+    pub fn set_thank_you(ctx: Context<SetThankYou>, name: String, message: String) -> Result<()> {
+        instructions::set_thank_you(ctx, name, message)
+    }
+
+    pub fn update_description(ctx: Context<UpdateDescription>, name: String, description: String) -> Result<()> {
+        instructions::update_description(ctx, name, description)
+    }
+
+    pub fn set_target(ctx: Context<SetTarget>, name: String, goal: u64) -> Result<()> {
+        instructions::set_target(ctx, name, goal)
+    }
+
+    pub fn extend_deadline(ctx: Context<ExtendDeadline>, name: String, additional_seconds: u32) -> Result<()> {
+        instructions::extend_deadline(ctx, name, additional_seconds)
+    }
+
+    pub fn freeze_metadata(ctx: Context<FreezeMetadata>, name: String) -> Result<()> {
+        instructions::freeze_metadata(ctx, name)
+    }
+
     pub fn donate(ctx: Context<Donate>, name: String, amount: u64) -> Result<()> {
         instructions::donate(ctx, name, amount)
     }
+
+    /// CPI-friendly donate: callers pass the campaign's `creator` and `name`
+    /// and the program checks the campaign account is the PDA derived from them.
+    pub fn donate_by_name(ctx: Context<DonateByName>, creator: Pubkey, name: String, amount: u64) -> Result<()> {
+        instructions::donate_by_name(ctx, creator, name, amount)
+    }
+
+    /// Not available on all-or-nothing or milestone campaigns, whose SOL-based
+    /// release rules would leave unrefundable tokens locked.
+    pub fn enable_spl(ctx: Context<EnableSpl>, name: String) -> Result<()> {
+        instructions::enable_spl(ctx, name)
+    }
+
+    /// SPL donations are not recorded per donor, so unlike SOL donations they
+    /// cannot be refunded. The admin releases them with `withdraw_spl`.
+    pub fn donate_spl(ctx: Context<DonateSpl>, name: String, amount: u64) -> Result<()> {
+        instructions::donate_spl(ctx, name, amount)
+    }
+
+    /// Pays `amount` tokens out of the campaign's token account, applying the
+    /// same authorization and fee rules as `withdraw`.
+    pub fn withdraw_spl(ctx: Context<WithdrawSpl>, name: String, amount: u64) -> Result<()> {
+        instructions::withdraw_spl(ctx, name, amount)
+    }
+
+    pub fn pause(ctx: Context<Pause>, name: String) -> Result<()> {
+        instructions::pause(ctx, name)
+    }
+
+    pub fn resume(ctx: Context<Resume>, name: String) -> Result<()> {
+        instructions::resume(ctx, name)
+    }
+
+    /// Permanently stops donations without closing the account, so its history is kept.
+    pub fn finalize(ctx: Context<Finalize>, name: String) -> Result<()> {
+        instructions::finalize(ctx, name)
+    }
+
+    /// Hands the campaign to `new_admin`. The PDA is seeded by `campaign.creator`,
+    /// which never changes, so the campaign address stays the same.
+    pub fn transfer_admin(ctx: Context<TransferAdmin>, name: String, new_admin: Pubkey) -> Result<()> {
+        instructions::transfer_admin(ctx, name, new_admin)
+    }
+
+    /// Lets `co_admin` withdraw alongside the admin. Only the admin can add or
+    /// remove co-admins, up to `MAX_CO_ADMINS`.
+    pub fn add_co_admin(ctx: Context<AddCoAdmin>, name: String, co_admin: Pubkey) -> Result<()> {
+        instructions::add_co_admin(ctx, name, co_admin)
+    }
+
+    pub fn remove_co_admin(ctx: Context<RemoveCoAdmin>, name: String, co_admin: Pubkey) -> Result<()> {
+        instructions::remove_co_admin(ctx, name, co_admin)
+    }
+
+    pub fn close(ctx: Context<Close>, name: String) -> Result<()> {
+        instructions::close(ctx, name)
+    }
+
+    /// Returns the donor's recorded SOL once the deadline has passed below goal.
+    /// If the admin withdrew before the deadline, the refund is capped at what
+    /// the campaign still holds.
+    pub fn refund(ctx: Context<Refund>, name: String) -> Result<()> {
+        instructions::refund(ctx, name)
+    }
+
+    /// Returns a donation record's rent to its donor once it can no longer be
+    /// refunded: the campaign was closed or re-created, or ended at its goal.
+    pub fn close_donation_record(ctx: Context<CloseDonationRecord>) -> Result<()> {
+        instructions::close_donation_record(ctx)
+    }
+
+    pub fn initialize_platform_config(ctx: Context<InitializePlatformConfig>) -> Result<()> {
+        instructions::initialize_platform_config(ctx)
+    }
+
+    pub fn set_platform_authority(ctx: Context<SetPlatformAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::set_platform_authority(ctx, new_authority)
+    }
+
+    /// Sets the fee charged on withdrawals from campaigns created from now on.
+    /// Existing campaigns keep the terms they were created with.
+    pub fn set_platform_fee(ctx: Context<SetPlatformFee>, fee_bps: u16, fee_collector: Pubkey) -> Result<()> {
+        instructions::set_platform_fee(ctx, fee_bps, fee_collector)
+    }
 }
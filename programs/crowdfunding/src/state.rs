@@ -1,16 +1,32 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::CampaignError;
 
 #[derive(Accounts)]
-#[instruction(name: String)]
+#[instruction(args: CreateCampaignArgs)]
 pub struct Create<'info> {
     #[account(
         init,
         payer = user,
-        space = 9000,
-        seeds = [b"CAMPAIGN_DEMO".as_ref(), user.key().as_ref(), name.as_ref()],
+        space = Campaign::space(&args.name, &args.description, &args.milestones, &args.category, &args.metadata_uri),
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), user.key().as_ref(), campaign_name_seed(&args.name)?],
         bump
     )]
     pub campaign: Account<'info, Campaign>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 8 + 8 + 1,
+        seeds = [b"GLOBAL_STATS".as_ref()],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        seeds = [b"PLATFORM_CONFIG".as_ref()],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -21,28 +37,428 @@ pub struct Create<'info> {
 pub struct Withdraw<'info> {
     #[account(
         mut,
-        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.admin.as_ref(), name.as_ref()],
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.can_withdraw(&admin.key()) @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    /// The campaign admin or one of its co_admins
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// CHECK: only receives lamports; must match campaign.fee_collector
+    #[account(mut, address = campaign.fee_collector)]
+    pub fee_collector: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct WithdrawAll<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.can_withdraw(&admin.key()) @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    /// The campaign admin or one of its co_admins
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// CHECK: only receives lamports; must match campaign.fee_collector
+    #[account(mut, address = campaign.fee_collector)]
+    pub fee_collector: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct Donate<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
         bump = campaign.bump
     )]
     pub campaign: Account<'info, Campaign>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 8 + 8 + 1,
+        seeds = [b"GLOBAL_STATS".as_ref()],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 + 1 + 8,
+        seeds = [b"DONATION".as_ref(), campaign.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub donation_record: Account<'info, DonationRecord>,
     #[account(mut)]
     pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 #[instruction(name: String)]
-pub struct Donate<'info> {
+pub struct SetThankYou<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        has_one = admin @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct UpdateDescription<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        has_one = admin @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct SetTarget<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        has_one = admin @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct ExtendDeadline<'info> {
     #[account(
         mut,
-        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.admin.as_ref(), name.as_ref()],
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        has_one = admin @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct FreezeMetadata<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        has_one = admin @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey, name: String)]
+pub struct DonateByName<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), creator.as_ref(), name.as_ref()],
         bump = campaign.bump
     )]
     pub campaign: Account<'info, Campaign>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 8 + 8 + 1,
+        seeds = [b"GLOBAL_STATS".as_ref()],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 + 1 + 8,
+        seeds = [b"DONATION".as_ref(), campaign.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub donation_record: Account<'info, DonationRecord>,
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct EnableSpl<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        has_one = admin @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub mint: Account<'info, Mint>,
+    // The ATA program is permissionless, so anyone may have created this already
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = campaign
+    )]
+    pub campaign_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct DonateSpl<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.spl_mint == Some(mint.key()) @ CampaignError::MintMismatch
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user
+    )]
+    pub donor_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = campaign
+    )]
+    pub campaign_token_account: Account<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct WithdrawSpl<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.can_withdraw(&admin.key()) @ CampaignError::Unauthorized,
+        constraint = campaign.spl_mint == Some(mint.key()) @ CampaignError::MintMismatch
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = campaign
+    )]
+    pub campaign_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = admin
+    )]
+    pub admin_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = campaign.fee_collector
+    )]
+    pub fee_collector_token_account: Account<'info, TokenAccount>,
+    /// The campaign admin or one of its co_admins
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct Pause<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        has_one = admin @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct Resume<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        has_one = admin @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct Finalize<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        has_one = admin @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct TransferAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        has_one = admin @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct AddCoAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        has_one = admin @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct RemoveCoAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        has_one = admin @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct Close<'info> {
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump,
+        has_one = admin @ CampaignError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// The campaign's associated token account; required once SPL donations are enabled
+    #[account(mut)]
+    pub campaign_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct Refund<'info> {
+    #[account(
+        mut,
+        seeds = [b"CAMPAIGN_DEMO".as_ref(), campaign.creator.as_ref(), name.as_ref()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+    #[account(
+        mut,
+        seeds = [b"DONATION".as_ref(), campaign.key().as_ref(), donor.key().as_ref()],
+        bump = donation_record.bump,
+        constraint = donation_record.campaign_id == campaign.campaign_id @ CampaignError::StaleDonationRecord,
+        close = donor
+    )]
+    pub donation_record: Account<'info, DonationRecord>,
+    #[account(mut)]
+    pub donor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDonationRecord<'info> {
+    /// CHECK: only read if it still holds the campaign the record belongs to;
+    /// the record's seeds tie it to this address
+    pub campaign: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"DONATION".as_ref(), campaign.key().as_ref(), donor.key().as_ref()],
+        bump = donation_record.bump,
+        close = donor
+    )]
+    pub donation_record: Account<'info, DonationRecord>,
+    #[account(mut)]
+    pub donor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePlatformConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 1 + 2 + 32,
+        seeds = [b"PLATFORM_CONFIG".as_ref()],
+        bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Crowdfunding>,
+    // Only the program's upgrade authority may bootstrap the config
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ CampaignError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPlatformAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"PLATFORM_CONFIG".as_ref()],
+        bump = platform_config.bump,
+        has_one = authority @ CampaignError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPlatformFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"PLATFORM_CONFIG".as_ref()],
+        bump = platform_config.bump,
+        has_one = authority @ CampaignError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    pub authority: Signer<'info>,
+}
+
 #[account]
 pub struct Campaign {
     pub admin: Pubkey,        // 32 bytes
@@ -50,4 +466,140 @@ pub struct Campaign {
     pub description: String,  // dynamic
     pub amount_donated: u64,  // 8 bytes
     pub bump: u8,            // 1 byte
+    pub currency_label: [u8; 8], // 8 bytes, NUL-padded
+    pub thank_you_message: String, // dynamic, up to MAX_THANK_YOU_LEN
+    pub goal: u64,            // 8 bytes, in lamports
+    pub end_time: i64,        // 8 bytes, unix timestamp
+    pub metadata_locked: bool, // 1 byte
+    pub creator: Pubkey,      // 32 bytes, PDA seed; unchanged by transfer_admin
+    pub status: CampaignStatus, // 1 byte
+    pub min_donation: u64,    // 8 bytes, in lamports; 0 disables the minimum
+    pub donation_count: u64,  // 8 bytes
+    pub spl_mint: Option<Pubkey>, // 33 bytes, set by enable_spl
+    pub spl_amount_donated: u64, // 8 bytes, in base units of spl_mint
+    pub finalized: bool,      // 1 byte
+    pub fee_bps: u16,         // 2 bytes, platform share of each withdrawal in basis points
+    pub fee_collector: Pubkey, // 32 bytes, platform fee recipient
+    pub milestones: Vec<u64>, // 4 + 8 * len bytes, cumulative thresholds, up to MAX_MILESTONES
+    pub milestones_withdrawn: u8, // 1 byte
+    pub goal_reached: bool,   // 1 byte, set once by the donation that first meets the goal
+    pub all_or_nothing: bool, // 1 byte, withdrawals wait until the goal is met
+    pub last_donation_at: i64, // 8 bytes, unix timestamp; 0 until the first donation
+    pub co_admins: Vec<Pubkey>, // 4 + 32 * len bytes, up to MAX_CO_ADMINS, may withdraw
+    pub category: String,     // dynamic, up to MAX_CATEGORY_LEN; empty if uncategorized
+    pub metadata_uri: String, // dynamic, up to MAX_METADATA_URI_LEN; off-chain JSON/IPFS content
+    pub campaign_id: u64,     // 8 bytes, unique per create, so a re-created campaign at the same address is distinct
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum CampaignStatus {
+    Active,
+    Paused,
+    Closed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateCampaignArgs {
+    pub name: String,
+    pub description: String,
+    pub currency_label: Option<[u8; 8]>,
+    pub thank_you_message: String,
+    pub goal: u64,
+    pub duration_seconds: i64,
+    pub min_donation: u64,
+    pub milestones: Vec<u64>,
+    pub all_or_nothing: bool,
+    pub category: String,
+    pub metadata_uri: String,
+}
+
+impl Campaign {
+    // thank_you_message and co_admins can change after create, so their maximums are reserved up front
+    pub fn space(name: &str, description: &str, milestones: &[u64], category: &str, metadata_uri: &str) -> usize {
+        8 + 32 + (4 + name.len()) + (4 + description.len()) + 8 + 1
+            + 8 + (4 + MAX_THANK_YOU_LEN) + 8 + 8 + 1 + 32 + 1 + 8 + 8
+            + 33 + 8 + 1 + 2 + 32 + (4 + 8 * milestones.len()) + 1 + 1 + 1 + 8
+            + (4 + 32 * MAX_CO_ADMINS) + (4 + category.len()) + (4 + metadata_uri.len()) + 8
+    }
+
+    pub fn can_withdraw(&self, key: &Pubkey) -> bool {
+        self.admin == *key || self.co_admins.contains(key)
+    }
+
+    pub fn goal_met(&self) -> bool {
+        self.amount_donated >= self.goal
+    }
+
+    // Goals and milestones are measured in lamports and tokens cannot be
+    // refunded, so tokens would stay locked in a campaign gated on either
+    pub fn accepts_spl(&self) -> bool {
+        !self.all_or_nothing && self.milestones.is_empty()
+    }
+
+    // The amount the next withdrawal must release, or None once no milestones
+    // remain. A campaign that ends at its goal releases the rest in one go.
+    pub fn next_milestone_tranche(&self, now: i64) -> Result<Option<u64>> {
+        if now > self.end_time && self.goal_met() {
+            return Ok(None);
+        }
+
+        let next = self.milestones_withdrawn as usize;
+        let Some(&threshold) = self.milestones.get(next) else {
+            return Ok(None);
+        };
+
+        if self.amount_donated < threshold {
+            return Err(CampaignError::MilestoneNotReached.into());
+        }
+
+        let released = if next == 0 { 0 } else { self.milestones[next - 1] };
+        Ok(Some(threshold - released))
+    }
+}
+
+// Solana caps each PDA seed at 32 bytes and the name is used as a seed
+#[constant]
+pub const MAX_NAME_LEN: u8 = 32;
+pub const DEFAULT_CURRENCY_LABEL: [u8; 8] = *b"SOL\0\0\0\0\0";
+pub const MAX_THANK_YOU_LEN: usize = 200;
+pub const MAX_FEE_BPS: u16 = 10_000;
+pub const MAX_MILESTONES: usize = 8;
+pub const MAX_CO_ADMINS: usize = 4;
+pub const MAX_CATEGORY_LEN: usize = 32;
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+#[account]
+pub struct DonationRecord {
+    pub donor: Pubkey,        // 32 bytes
+    pub campaign: Pubkey,     // 32 bytes
+    pub amount: u64,          // 8 bytes
+    pub bump: u8,            // 1 byte
+    pub campaign_id: u64,     // 8 bytes, the Campaign::campaign_id the amount belongs to
+}
+
+#[account]
+pub struct PlatformConfig {
+    pub authority: Pubkey,    // 32 bytes
+    pub bump: u8,            // 1 byte
+    pub fee_bps: u16,         // 2 bytes, copied into each campaign at create
+    pub fee_collector: Pubkey, // 32 bytes, copied into each campaign at create
+}
+
+#[account]
+pub struct GlobalStats {
+    pub total_campaigns: u64, // 8 bytes
+    pub total_raised: u64,    // 8 bytes
+    pub bump: u8,            // 1 byte
+}
+
+// Runs while Anchor derives the `Create` PDA, so an over-long or unsafe name
+// is rejected with a clear error before the runtime's seed-length check.
+pub fn campaign_name_seed(name: &str) -> Result<&[u8]> {
+    if name.len() > usize::from(MAX_NAME_LEN) {
+        return Err(CampaignError::NameTooLong.into());
+    }
+    if name.contains('\0') {
+        return Err(CampaignError::InvalidName.into());
+    }
+    Ok(name.as_bytes())
 }
@@ -1,16 +1,1122 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Crowdfunding } from "../target/types/crowdfunding";
+import { CpiCaller } from "../target/types/cpi_caller";
+import { assert } from "chai";
 
 describe("crowdfunding", () => {
   // Configure the client to use the local cluster.
   anchor.setProvider(anchor.AnchorProvider.env());
 
   const program = anchor.workspace.crowdfunding as Program<Crowdfunding>;
+  const cpiCaller = anchor.workspace.cpiCaller as Program<CpiCaller>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const [platformConfig] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("PLATFORM_CONFIG")],
+    program.programId
+  );
+
+  const campaignPda = (admin: anchor.web3.PublicKey, name: string) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("CAMPAIGN_DEMO"), admin.toBuffer(), Buffer.from(name)],
+      program.programId
+    )[0];
+
+  const label = (text: string) => {
+    const bytes = Buffer.alloc(8);
+    bytes.write(text);
+    return Array.from(bytes);
+  };
+
+  const [globalStats] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("GLOBAL_STATS")],
+    program.programId
+  );
+
+  const fundedKeypair = async (lamports = 100_000_000) => {
+    const keypair = anchor.web3.Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: keypair.publicKey,
+          lamports,
+        })
+      )
+    );
+    return keypair;
+  };
+
+  const donationRecordPda = (
+    campaign: anchor.web3.PublicKey,
+    donor: anchor.web3.PublicKey
+  ) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("DONATION"), campaign.toBuffer(), donor.toBuffer()],
+      program.programId
+    )[0];
+
+  // Minimal SPL Token helpers built from raw instructions, so the tests need
+  // no dependency beyond @coral-xyz/anchor
+  const { TOKEN_PROGRAM_ID, ASSOCIATED_PROGRAM_ID, associatedAddress } =
+    anchor.utils.token;
+
+  const createMint = async () => {
+    const mint = anchor.web3.Keypair.generate();
+    const data = Buffer.alloc(35);
+    data.writeUInt8(20, 0); // InitializeMint2, 0 decimals, no freeze authority
+    provider.wallet.publicKey.toBuffer().copy(data, 2);
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.createAccount({
+          fromPubkey: provider.wallet.publicKey,
+          newAccountPubkey: mint.publicKey,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(82),
+          space: 82,
+          programId: TOKEN_PROGRAM_ID,
+        }),
+        new anchor.web3.TransactionInstruction({
+          programId: TOKEN_PROGRAM_ID,
+          keys: [{ pubkey: mint.publicKey, isSigner: false, isWritable: true }],
+          data,
+        })
+      ),
+      [mint]
+    );
+    return mint.publicKey;
+  };
+
+  const createTokenAccount = async (
+    owner: anchor.web3.PublicKey,
+    mint: anchor.web3.PublicKey
+  ) => {
+    const address = associatedAddress({ mint, owner });
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        new anchor.web3.TransactionInstruction({
+          programId: ASSOCIATED_PROGRAM_ID,
+          keys: [
+            { pubkey: provider.wallet.publicKey, isSigner: true, isWritable: true },
+            { pubkey: address, isSigner: false, isWritable: true },
+            { pubkey: owner, isSigner: false, isWritable: false },
+            { pubkey: mint, isSigner: false, isWritable: false },
+            { pubkey: anchor.web3.SystemProgram.programId, isSigner: false, isWritable: false },
+            { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+          ],
+          data: Buffer.from([1]), // CreateIdempotent
+        })
+      )
+    );
+    return address;
+  };
+
+  const mintTo = async (
+    mint: anchor.web3.PublicKey,
+    destination: anchor.web3.PublicKey,
+    amount: number
+  ) => {
+    const data = Buffer.concat([
+      Buffer.from([7]), // MintTo
+      new anchor.BN(amount).toArrayLike(Buffer, "le", 8),
+    ]);
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        new anchor.web3.TransactionInstruction({
+          programId: TOKEN_PROGRAM_ID,
+          keys: [
+            { pubkey: mint, isSigner: false, isWritable: true },
+            { pubkey: destination, isSigner: false, isWritable: true },
+            { pubkey: provider.wallet.publicKey, isSigner: true, isWritable: false },
+          ],
+          data,
+        })
+      )
+    );
+  };
+
+  const tokenBalance = async (address: anchor.web3.PublicKey) =>
+    Number((await provider.connection.getTokenAccountBalance(address)).value.amount);
+
+  // Sets up a campaign that accepts `mint`, with its token account created
+  // up front the way any third party could
+  const createSplCampaign = async (name: string, mint: anchor.web3.PublicKey) => {
+    const campaign = await createCampaign(name);
+    const campaignTokenAccount = await createTokenAccount(campaign, mint);
+    await program.methods
+      .enableSpl(name)
+      .accountsPartial({
+        campaign,
+        mint,
+        campaignTokenAccount,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+    return { campaign, campaignTokenAccount };
+  };
+
+  // Waits until the cluster clock is past the campaign's end_time
+  const waitForDeadline = async (campaign: anchor.web3.PublicKey) => {
+    const { endTime } = await program.account.campaign.fetch(campaign);
+    for (;;) {
+      const time = await provider.connection.getBlockTime(
+        await provider.connection.getSlot()
+      );
+      if (time !== null && time > endTime.toNumber()) return;
+      await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+  };
+
+  const createCampaign = async (
+    name: string,
+    {
+      admin = null,
+      currencyLabel = null,
+      thankYouMessage = "",
+      goal = 1_000_000_000,
+      durationSeconds = 3600,
+      minDonation = 0,
+      milestones = [],
+      allOrNothing = false,
+      category = "",
+      metadataUri = "",
+    }: {
+      admin?: anchor.web3.Keypair | null;
+      currencyLabel?: number[] | null;
+      thankYouMessage?: string;
+      goal?: number;
+      durationSeconds?: number | anchor.BN;
+      minDonation?: number;
+      milestones?: number[];
+      allOrNothing?: boolean;
+      category?: string;
+      metadataUri?: string;
+    } = {}
+  ) => {
+    const user = admin ? admin.publicKey : provider.wallet.publicKey;
+    await program.methods
+      .create({
+        name,
+        description: "test campaign",
+        currencyLabel,
+        thankYouMessage,
+        goal: new anchor.BN(goal),
+        durationSeconds: new anchor.BN(durationSeconds),
+        minDonation: new anchor.BN(minDonation),
+        milestones: milestones.map((m) => new anchor.BN(m)),
+        allOrNothing,
+        category,
+        metadataUri,
+      })
+      .accountsPartial({
+        campaign: campaignPda(user, name),
+        globalStats,
+        platformConfig,
+        user,
+      })
+      .signers(admin ? [admin] : [])
+      .rpc();
+    return campaignPda(user, name);
+  };
+
+  // Campaigns copy their fee terms from the platform config at create
+  before(async () => {
+    const [programData] = anchor.web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    await program.methods
+      .initializePlatformConfig()
+      .accountsPartial({
+        platformConfig,
+        program: program.programId,
+        programData,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+  });
 
   it("Is initialized!", async () => {
     // Add your test here.
     const tx = await program.methods.initialize().rpc();
     console.log("Your transaction signature", tx);
   });
+
+  it("Only the current platform authority can rotate it", async () => {
+    const newAuthority = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .setPlatformAuthority(newAuthority.publicKey)
+        .accountsPartial({ platformConfig, authority: newAuthority.publicKey })
+        .signers([newAuthority])
+        .rpc();
+      assert.fail("rotation by a non-authority should fail");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+
+    await program.methods
+      .setPlatformAuthority(newAuthority.publicKey)
+      .accountsPartial({ platformConfig, authority: provider.wallet.publicKey })
+      .rpc();
+    let config = await program.account.platformConfig.fetch(platformConfig);
+    assert.ok(config.authority.equals(newAuthority.publicKey));
+
+    await program.methods
+      .setPlatformAuthority(provider.wallet.publicKey)
+      .accountsPartial({ platformConfig, authority: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc();
+    config = await program.account.platformConfig.fetch(platformConfig);
+    assert.ok(config.authority.equals(provider.wallet.publicKey));
+  });
+
+  it("Copies the platform fee into new campaigns", async () => {
+    const authority = provider.wallet.publicKey;
+    const collector = anchor.web3.Keypair.generate().publicKey;
+    const stranger = await fundedKeypair();
+    try {
+      await program.methods
+        .setPlatformFee(500, stranger.publicKey)
+        .accountsPartial({ platformConfig, authority: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      assert.fail("only the platform authority should set the fee");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+
+    await program.methods
+      .setPlatformFee(250, collector)
+      .accountsPartial({ platformConfig, authority })
+      .rpc();
+    const campaign = await createCampaign(`fee-${Date.now()}`);
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.feeBps, 250);
+    assert.ok(account.feeCollector.equals(collector));
+
+    // Restore the defaults the other tests rely on
+    await program.methods
+      .setPlatformFee(0, authority)
+      .accountsPartial({ platformConfig, authority })
+      .rpc();
+  });
+
+  it("Donates by creator and name", async () => {
+    const name = `by-name-${Date.now()}`;
+    const campaign = await createCampaign(name);
+    const admin = provider.wallet.publicKey;
+
+    await program.methods
+      .donateByName(admin, name, new anchor.BN(1_000_000))
+      .accountsPartial({ campaign, user: admin })
+      .rpc();
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.amountDonated.toNumber(), 1_000_000);
+    assert.isAbove(account.lastDonationAt.toNumber(), 0);
+
+    try {
+      await program.methods
+        .donateByName(anchor.web3.Keypair.generate().publicKey, name, new anchor.BN(1_000_000))
+        .accountsPartial({ campaign, user: admin })
+        .rpc();
+      assert.fail("donating with the wrong creator should fail");
+    } catch (err) {
+      assert.include(err.toString(), "ConstraintSeeds");
+    }
+  });
+
+  it("Accepts donate_by_name from another program via CPI", async () => {
+    const name = `cpi-${Date.now()}`;
+    const campaign = await createCampaign(name);
+    const user = provider.wallet.publicKey;
+
+    await cpiCaller.methods
+      .donate(user, name, new anchor.BN(1_000_000))
+      .accountsPartial({
+        campaign,
+        globalStats,
+        donationRecord: donationRecordPda(campaign, user),
+        user,
+        crowdfundingProgram: program.programId,
+      })
+      .rpc();
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.amountDonated.toNumber(), 1_000_000);
+  });
+
+  it("Stores the currency label and defaults to SOL", async () => {
+    const usdc = await createCampaign(`usdc-${Date.now()}`, {
+      currencyLabel: label("USDC"),
+    });
+    let account = await program.account.campaign.fetch(usdc);
+    assert.deepEqual(account.currencyLabel, label("USDC"));
+
+    const sol = await createCampaign(`sol-${Date.now()}`);
+    account = await program.account.campaign.fetch(sol);
+    assert.deepEqual(account.currencyLabel, label("SOL"));
+  });
+
+  it("Stores the category and rejects an over-long one", async () => {
+    const campaign = await createCampaign(`category-${Date.now()}`, {
+      category: "education",
+    });
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.category, "education");
+
+    try {
+      await createCampaign(`long-category-${Date.now()}`, {
+        category: "x".repeat(33),
+      });
+      assert.fail("an over-long category should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "CategoryTooLong");
+    }
+  });
+
+  it("Stores the metadata URI", async () => {
+    const uri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    const campaign = await createCampaign(`uri-${Date.now()}`, {
+      metadataUri: uri,
+    });
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.metadataUri, uri);
+  });
+
+  it("Round-trips the thank-you message and enforces its length", async () => {
+    const name = `thanks-${Date.now()}`;
+    const campaign = await createCampaign(name, {
+      thankYouMessage: "Thank you!",
+    });
+    let account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.thankYouMessage, "Thank you!");
+
+    await program.methods
+      .setThankYou(name, "Much appreciated")
+      .accountsPartial({ campaign, admin: provider.wallet.publicKey })
+      .rpc();
+    account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.thankYouMessage, "Much appreciated");
+
+    try {
+      await program.methods
+        .setThankYou(name, "x".repeat(201))
+        .accountsPartial({ campaign, admin: provider.wallet.publicKey })
+        .rpc();
+      assert.fail("an over-long message should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "ThankYouMessageTooLong");
+    }
+  });
+
+  it("Updates the description only within the allocated space", async () => {
+    const name = `describe-${Date.now()}`;
+    const campaign = await createCampaign(name);
+    const admin = provider.wallet.publicKey;
+
+    await program.methods
+      .updateDescription(name, "new text")
+      .accountsPartial({ campaign, admin })
+      .rpc();
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.description, "new text");
+
+    // The account was sized for "test campaign"; the thank-you and co-admin
+    // reserve must not absorb a longer description
+    try {
+      await program.methods
+        .updateDescription(name, "x".repeat(100))
+        .accountsPartial({ campaign, admin })
+        .rpc();
+      assert.fail("a description that no longer fits should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "DescriptionTooLong");
+    }
+  });
+
+  it("Counts campaigns across admins in the global stats", async () => {
+    await createCampaign(`stats-a-${Date.now()}`);
+    const before = await program.account.globalStats.fetch(globalStats);
+
+    const otherAdmin = await fundedKeypair();
+    const name = `stats-b-${Date.now()}`;
+    const campaign = await createCampaign(name, { admin: otherAdmin });
+    await program.methods
+      .donate(name, new anchor.BN(500_000))
+      .accountsPartial({ campaign, globalStats, user: provider.wallet.publicKey })
+      .rpc();
+
+    const after = await program.account.globalStats.fetch(globalStats);
+    assert.equal(
+      after.totalCampaigns.toNumber(),
+      before.totalCampaigns.toNumber() + 1
+    );
+    assert.equal(
+      after.totalRaised.toNumber(),
+      before.totalRaised.toNumber() + 500_000
+    );
+  });
+
+  it("Blocks metadata updates once frozen", async () => {
+    const name = `frozen-${Date.now()}`;
+    const campaign = await createCampaign(name);
+    const admin = provider.wallet.publicKey;
+
+    await program.methods
+      .setTarget(name, new anchor.BN(2_000_000_000))
+      .accountsPartial({ campaign, admin })
+      .rpc();
+    await program.methods
+      .extendDeadline(name, 60)
+      .accountsPartial({ campaign, admin })
+      .rpc();
+    let account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.goal.toNumber(), 2_000_000_000);
+
+    await program.methods
+      .freezeMetadata(name)
+      .accountsPartial({ campaign, admin })
+      .rpc();
+    account = await program.account.campaign.fetch(campaign);
+    assert.isTrue(account.metadataLocked);
+
+    for (const update of [
+      program.methods.setTarget(name, new anchor.BN(1)),
+      program.methods.extendDeadline(name, 60),
+    ]) {
+      try {
+        await update.accountsPartial({ campaign, admin }).rpc();
+        assert.fail("updates should be rejected once metadata is locked");
+      } catch (err) {
+        assert.include(err.toString(), "MetadataLocked");
+      }
+    }
+  });
+
+  it("Rejects non-positive and overflowing durations", async () => {
+    for (const durationSeconds of [
+      0,
+      -1,
+      new anchor.BN("9223372036854775807"),
+    ]) {
+      try {
+        await createCampaign(`duration-${Date.now()}`, { durationSeconds });
+        assert.fail("the duration should be rejected");
+      } catch (err) {
+        assert.include(err.toString(), "InvalidDuration");
+      }
+    }
+  });
+
+  it("Rejects a campaign name containing a NUL byte", async () => {
+    try {
+      await createCampaign("bad\0name");
+      assert.fail("a name with a NUL byte should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidName");
+    }
+  });
+
+  it("Accepts a name at MAX_NAME_LEN and rejects one byte over", async () => {
+    const maxNameLen = Number(
+      program.idl.constants.find((c) => c.name === "MAX_NAME_LEN").value
+    );
+    const stem = `${Date.now()}`;
+
+    const atLimit = stem + "a".repeat(maxNameLen - stem.length);
+    const campaign = await createCampaign(atLimit);
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.name, atLimit);
+
+    // The PDA can't be derived client-side for an over-long seed, so pass
+    // any address and let the program reject the name first.
+    try {
+      await program.methods
+        .create({
+          name: stem + "b".repeat(maxNameLen + 1 - stem.length),
+          description: "test campaign",
+          currencyLabel: null,
+          thankYouMessage: "",
+          goal: new anchor.BN(1_000_000_000),
+          durationSeconds: new anchor.BN(3600),
+          minDonation: new anchor.BN(0),
+          milestones: [],
+          allOrNothing: false,
+          category: "",
+          metadataUri: "",
+        })
+        .accountsPartial({
+          campaign: anchor.web3.Keypair.generate().publicKey,
+          globalStats,
+          platformConfig,
+          user: provider.wallet.publicKey,
+        })
+        .rpc();
+      assert.fail("a name over MAX_NAME_LEN should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "NameTooLong");
+    }
+  });
+
+  it("Rejects donations while paused and accepts them after resume", async () => {
+    const name = `paused-${Date.now()}`;
+    const campaign = await createCampaign(name);
+    const user = provider.wallet.publicKey;
+    const donate = () =>
+      program.methods
+        .donate(name, new anchor.BN(1_000_000))
+        .accountsPartial({ campaign, globalStats, user })
+        .rpc();
+
+    await program.methods.pause(name).accountsPartial({ campaign, admin: user }).rpc();
+    try {
+      await donate();
+      assert.fail("donations should be rejected while paused");
+    } catch (err) {
+      assert.include(err.toString(), "CampaignNotActive");
+    }
+
+    await program.methods.resume(name).accountsPartial({ campaign, admin: user }).rpc();
+    await donate();
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.amountDonated.toNumber(), 1_000_000);
+  });
+
+  it("Only the admin can finalize, and finalized campaigns reject donations", async () => {
+    const name = `final-${Date.now()}`;
+    const campaign = await createCampaign(name);
+    const user = provider.wallet.publicKey;
+
+    const stranger = await fundedKeypair();
+    try {
+      await program.methods
+        .finalize(name)
+        .accountsPartial({ campaign, admin: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      assert.fail("finalize by a non-admin should fail");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+
+    await program.methods.finalize(name).accountsPartial({ campaign, admin: user }).rpc();
+    try {
+      await program.methods
+        .donate(name, new anchor.BN(1_000_000))
+        .accountsPartial({ campaign, globalStats, user })
+        .rpc();
+      assert.fail("donations should be rejected once finalized");
+    } catch (err) {
+      assert.include(err.toString(), "CampaignFinalized");
+    }
+  });
+
+  it("Flips goal_reached on the donation that meets the goal", async () => {
+    const name = `goal-${Date.now()}`;
+    const campaign = await createCampaign(name, { goal: 1_000_000 });
+    const user = provider.wallet.publicKey;
+    const donate = (amount: number) =>
+      program.methods
+        .donate(name, new anchor.BN(amount))
+        .accountsPartial({ campaign, globalStats, user })
+        .rpc();
+
+    await donate(600_000);
+    let account = await program.account.campaign.fetch(campaign);
+    assert.isFalse(account.goalReached);
+
+    await donate(600_000);
+    account = await program.account.campaign.fetch(campaign);
+    assert.isTrue(account.goalReached);
+
+    const setTarget = (goal: number) =>
+      program.methods
+        .setTarget(name, new anchor.BN(goal))
+        .accountsPartial({ campaign, admin: user })
+        .rpc();
+    await setTarget(2_000_000);
+    account = await program.account.campaign.fetch(campaign);
+    assert.isFalse(account.goalReached);
+    await setTarget(1_200_000);
+    account = await program.account.campaign.fetch(campaign);
+    assert.isTrue(account.goalReached);
+  });
+
+  it("Holds all-or-nothing funds until the goal is met", async () => {
+    const name = `aon-${Date.now()}`;
+    const campaign = await createCampaign(name, {
+      goal: 1_000_000,
+      allOrNothing: true,
+    });
+    const user = provider.wallet.publicKey;
+    const donate = (amount: number) =>
+      program.methods
+        .donate(name, new anchor.BN(amount))
+        .accountsPartial({ campaign, globalStats, user })
+        .rpc();
+    const withdraw = () =>
+      program.methods
+        .withdraw(name, new anchor.BN(500_000))
+        .accountsPartial({ campaign, admin: user, feeCollector: user })
+        .rpc();
+
+    await donate(500_000);
+    try {
+      await program.methods
+        .setTarget(name, new anchor.BN(0))
+        .accountsPartial({ campaign, admin: user })
+        .rpc();
+      assert.fail("the goal of an all-or-nothing campaign should be frozen");
+    } catch (err) {
+      assert.include(err.toString(), "GoalLocked");
+    }
+    try {
+      await withdraw();
+      assert.fail("withdrawals should wait until the goal is met");
+    } catch (err) {
+      assert.include(err.toString(), "GoalNotReached");
+    }
+
+    await donate(500_000);
+    await withdraw();
+  });
+
+  it("Lets co-admins withdraw until they are removed", async () => {
+    const name = `co-admin-${Date.now()}`;
+    const campaign = await createCampaign(name);
+    const admin = provider.wallet.publicKey;
+    const coAdmin = await fundedKeypair();
+    const withdrawAs = (signer: anchor.web3.Keypair) =>
+      program.methods
+        .withdraw(name, new anchor.BN(100_000))
+        .accountsPartial({ campaign, admin: signer.publicKey, feeCollector: admin })
+        .signers([signer])
+        .rpc();
+
+    await program.methods
+      .donate(name, new anchor.BN(1_000_000))
+      .accountsPartial({ campaign, globalStats, user: admin })
+      .rpc();
+    await program.methods
+      .addCoAdmin(name, coAdmin.publicKey)
+      .accountsPartial({ campaign, admin })
+      .rpc();
+    await withdrawAs(coAdmin);
+
+    await program.methods
+      .removeCoAdmin(name, coAdmin.publicKey)
+      .accountsPartial({ campaign, admin })
+      .rpc();
+    try {
+      await withdrawAs(coAdmin);
+      assert.fail("a removed co-admin should not be able to withdraw");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+  });
+
+  it("Refunds donors only after the deadline of a campaign that missed its goal", async () => {
+    const user = provider.wallet.publicKey;
+    const donateAndRefund = async (name: string, goal: number) => {
+      const campaign = await createCampaign(name, { goal, durationSeconds: 2 });
+      await program.methods
+        .donate(name, new anchor.BN(500_000))
+        .accountsPartial({ campaign, globalStats, user })
+        .rpc();
+      const refund = () =>
+        program.methods
+          .refund(name)
+          .accountsPartial({
+            campaign,
+            donationRecord: donationRecordPda(campaign, user),
+            donor: user,
+          })
+          .rpc();
+      return { campaign, refund };
+    };
+
+    const missed = await donateAndRefund(`refund-${Date.now()}`, 1_000_000);
+    try {
+      await missed.refund();
+      assert.fail("refunds should wait for the deadline");
+    } catch (err) {
+      assert.include(err.toString(), "CampaignActive");
+    }
+
+    const met = await donateAndRefund(`refund-met-${Date.now()}`, 500_000);
+    await waitForDeadline(met.campaign);
+    try {
+      await met.refund();
+      assert.fail("refunds should be refused once the goal is met");
+    } catch (err) {
+      assert.include(err.toString(), "GoalMet");
+    }
+
+    await waitForDeadline(missed.campaign);
+    await missed.refund();
+    const account = await program.account.campaign.fetch(missed.campaign);
+    assert.equal(account.amountDonated.toNumber(), 0);
+    const record = await program.account.donationRecord.fetchNullable(
+      donationRecordPda(missed.campaign, user)
+    );
+    assert.isNull(record, "the refunded record should be closed");
+  });
+
+  it("Lets donors close their record once it can no longer be refunded", async () => {
+    const name = `record-close-${Date.now()}`;
+    const campaign = await createCampaign(name, {
+      goal: 500_000,
+      durationSeconds: 2,
+    });
+    const user = provider.wallet.publicKey;
+    const donationRecord = donationRecordPda(campaign, user);
+    const closeRecord = () =>
+      program.methods
+        .closeDonationRecord()
+        .accountsPartial({ campaign, donationRecord, donor: user })
+        .rpc();
+
+    await program.methods
+      .donate(name, new anchor.BN(500_000))
+      .accountsPartial({ campaign, globalStats, user })
+      .rpc();
+    try {
+      await closeRecord();
+      assert.fail("the record should stay while the campaign is running");
+    } catch (err) {
+      assert.include(err.toString(), "DonationRecordInUse");
+    }
+
+    await waitForDeadline(campaign);
+    await closeRecord();
+    assert.isNull(await program.account.donationRecord.fetchNullable(donationRecord));
+  });
+
+  it("Keeps the admin out of refund funds and caps refunds at what is left", async () => {
+    const name = `refund-partial-${Date.now()}`;
+    const campaign = await createCampaign(name, {
+      goal: 1_000_000,
+      durationSeconds: 2,
+    });
+    const user = provider.wallet.publicKey;
+    const withdraw = () =>
+      program.methods
+        .withdraw(name, new anchor.BN(200_000))
+        .accountsPartial({ campaign, admin: user, feeCollector: user })
+        .rpc();
+
+    await program.methods
+      .donate(name, new anchor.BN(500_000))
+      .accountsPartial({ campaign, globalStats, user })
+      .rpc();
+    await withdraw();
+
+    await waitForDeadline(campaign);
+    try {
+      await withdraw();
+      assert.fail("withdrawals should stop once the deadline passes below goal");
+    } catch (err) {
+      assert.include(err.toString(), "RefundsOpen");
+    }
+
+    const before = await provider.connection.getBalance(campaign);
+    await program.methods
+      .refund(name)
+      .accountsPartial({
+        campaign,
+        donationRecord: donationRecordPda(campaign, user),
+        donor: user,
+      })
+      .rpc();
+    const after = await provider.connection.getBalance(campaign);
+    assert.equal(before - after, 300_000);
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.amountDonated.toNumber(), 200_000);
+  });
+
+  it("Rejects goal and deadline changes after the deadline", async () => {
+    const name = `ended-${Date.now()}`;
+    const campaign = await createCampaign(name, { durationSeconds: 1 });
+    const admin = provider.wallet.publicKey;
+    await waitForDeadline(campaign);
+
+    for (const update of [
+      program.methods.setTarget(name, new anchor.BN(1)),
+      program.methods.extendDeadline(name, 60),
+    ]) {
+      try {
+        await update.accountsPartial({ campaign, admin }).rpc();
+        assert.fail("updates should be rejected once the campaign has ended");
+      } catch (err) {
+        assert.include(err.toString(), "CampaignEnded");
+      }
+    }
+  });
+
+  it("Releases milestone tranches only once each threshold is crossed", async () => {
+    try {
+      await createCampaign(`zero-milestone-${Date.now()}`, {
+        milestones: [0, 1_000_000],
+      });
+      assert.fail("a zero first milestone should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidMilestones");
+    }
+
+    const name = `milestones-${Date.now()}`;
+    const campaign = await createCampaign(name, {
+      milestones: [1_000_000, 2_000_000],
+    });
+    const user = provider.wallet.publicKey;
+    const donate = () =>
+      program.methods
+        .donate(name, new anchor.BN(1_000_000))
+        .accountsPartial({ campaign, globalStats, user })
+        .rpc();
+    const withdraw = () =>
+      program.methods
+        .withdraw(name, new anchor.BN(1_000_000))
+        .accountsPartial({ campaign, admin: user, feeCollector: user })
+        .rpc();
+
+    try {
+      await withdraw();
+      assert.fail("withdrawals should wait for the first milestone");
+    } catch (err) {
+      assert.include(err.toString(), "MilestoneNotReached");
+    }
+
+    await donate();
+    await program.methods
+      .withdrawAll(name)
+      .accountsPartial({ campaign, admin: user, feeCollector: user })
+      .rpc();
+    let account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.milestonesWithdrawn, 1);
+
+    try {
+      await withdraw();
+      assert.fail("the second tranche should wait for its milestone");
+    } catch (err) {
+      assert.include(err.toString(), "MilestoneNotReached");
+    }
+
+    await donate();
+    await withdraw();
+    account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.milestonesWithdrawn, 2);
+  });
+
+  it("Releases the rest of a milestone campaign once it ends at its goal", async () => {
+    try {
+      await createCampaign(`milestone-over-goal-${Date.now()}`, {
+        goal: 1_000_000,
+        milestones: [1_000_000, 2_000_000],
+      });
+      assert.fail("a milestone above the goal should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidMilestones");
+    }
+
+    const name = `milestone-end-${Date.now()}`;
+    const campaign = await createCampaign(name, {
+      goal: 2_000_000,
+      durationSeconds: 2,
+      milestones: [1_000_000, 2_000_000],
+    });
+    const user = provider.wallet.publicKey;
+    const withdrawAll = () =>
+      program.methods
+        .withdrawAll(name)
+        .accountsPartial({ campaign, admin: user, feeCollector: user })
+        .rpc();
+
+    await program.methods
+      .donate(name, new anchor.BN(1_500_000))
+      .accountsPartial({ campaign, globalStats, user })
+      .rpc();
+    await withdrawAll();
+    // Lowering the goal leaves the second milestone out of reach
+    await program.methods
+      .setTarget(name, new anchor.BN(1_500_000))
+      .accountsPartial({ campaign, admin: user })
+      .rpc();
+    try {
+      await withdrawAll();
+      assert.fail("the second tranche should wait while the campaign runs");
+    } catch (err) {
+      assert.include(err.toString(), "MilestoneNotReached");
+    }
+
+    await waitForDeadline(campaign);
+    const before = await provider.connection.getBalance(campaign);
+    await withdrawAll();
+    const after = await provider.connection.getBalance(campaign);
+    assert.equal(before - after, 500_000);
+  });
+
+  it("Starts donation records over when a closed campaign is re-created", async () => {
+    const name = `recreate-${Date.now()}`;
+    const user = provider.wallet.publicKey;
+    const donate = (campaign: anchor.web3.PublicKey, amount: number) =>
+      program.methods
+        .donate(name, new anchor.BN(amount))
+        .accountsPartial({ campaign, globalStats, user })
+        .rpc();
+
+    let campaign = await createCampaign(name);
+    await donate(campaign, 1_000_000);
+    await program.methods
+      .withdrawAll(name)
+      .accountsPartial({ campaign, admin: user, feeCollector: user })
+      .rpc();
+    await program.methods.close(name).accountsPartial({ campaign, admin: user }).rpc();
+
+    campaign = await createCampaign(name);
+    await donate(campaign, 500_000);
+    const record = await program.account.donationRecord.fetch(
+      donationRecordPda(campaign, user)
+    );
+    assert.equal(record.amount.toNumber(), 500_000);
+  });
+
+  it("Accepts SPL donations in the enabled mint and releases them with the fee", async () => {
+    const user = provider.wallet.publicKey;
+    const collector = anchor.web3.Keypair.generate().publicKey;
+    await program.methods
+      .setPlatformFee(1_000, collector)
+      .accountsPartial({ platformConfig, authority: user })
+      .rpc();
+    const mint = await createMint();
+    const name = `spl-${Date.now()}`;
+    const { campaign, campaignTokenAccount } = await createSplCampaign(name, mint);
+    await program.methods
+      .setPlatformFee(0, user)
+      .accountsPartial({ platformConfig, authority: user })
+      .rpc();
+
+    const donorTokenAccount = await createTokenAccount(user, mint);
+    await mintTo(mint, donorTokenAccount, 1_000);
+    await program.methods
+      .donateSpl(name, new anchor.BN(400))
+      .accountsPartial({
+        campaign,
+        mint,
+        donorTokenAccount,
+        campaignTokenAccount,
+        user,
+      })
+      .rpc();
+    let account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.splAmountDonated.toNumber(), 400);
+    assert.ok(account.splMint.equals(mint));
+
+    const otherMint = await createMint();
+    const otherTokenAccount = await createTokenAccount(user, otherMint);
+    await mintTo(otherMint, otherTokenAccount, 1_000);
+    try {
+      await program.methods
+        .donateSpl(name, new anchor.BN(100))
+        .accountsPartial({
+          campaign,
+          mint: otherMint,
+          donorTokenAccount: otherTokenAccount,
+          campaignTokenAccount,
+          user,
+        })
+        .rpc();
+      assert.fail("donations in another mint should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "MintMismatch");
+    }
+
+    const feeTokenAccount = await createTokenAccount(collector, mint);
+    await program.methods
+      .withdrawSpl(name, new anchor.BN(400))
+      .accountsPartial({
+        campaign,
+        mint,
+        campaignTokenAccount,
+        adminTokenAccount: donorTokenAccount,
+        feeCollectorTokenAccount: feeTokenAccount,
+        admin: user,
+      })
+      .rpc();
+    assert.equal(await tokenBalance(campaignTokenAccount), 0);
+    assert.equal(await tokenBalance(donorTokenAccount), 600 + 360);
+    assert.equal(await tokenBalance(feeTokenAccount), 40);
+  });
+
+  it("Keeps SPL donations out of all-or-nothing and milestone campaigns", async () => {
+    const mint = await createMint();
+    const gated: { suffix: string; allOrNothing?: boolean; milestones?: number[] }[] = [
+      { suffix: "aon", allOrNothing: true },
+      { suffix: "milestones", milestones: [1_000_000] },
+    ];
+    for (const { suffix, allOrNothing, milestones } of gated) {
+      const name = `spl-${suffix}-${Date.now()}`;
+      const campaign = await createCampaign(name, { allOrNothing, milestones });
+      try {
+        await program.methods
+          .enableSpl(name)
+          .accountsPartial({
+            campaign,
+            mint,
+            campaignTokenAccount: await createTokenAccount(campaign, mint),
+            admin: provider.wallet.publicKey,
+          })
+          .rpc();
+        assert.fail("SPL donations should be refused on gated campaigns");
+      } catch (err) {
+        assert.include(err.toString(), "SplNotSupported");
+      }
+    }
+  });
+
+  it("Closes a campaign and its token account only once the tokens are withdrawn", async () => {
+    const user = provider.wallet.publicKey;
+    const mint = await createMint();
+    const name = `spl-close-${Date.now()}`;
+    const { campaign, campaignTokenAccount } = await createSplCampaign(name, mint);
+    await mintTo(mint, campaignTokenAccount, 10);
+
+    try {
+      await program.methods
+        .close(name)
+        .accountsPartial({ campaign, admin: user, campaignTokenAccount })
+        .rpc();
+      assert.fail("close should wait until the tokens are withdrawn");
+    } catch (err) {
+      assert.include(err.toString(), "CampaignNotEmpty");
+    }
+
+    try {
+      await program.methods
+        .close(name)
+        .accountsPartial({ campaign, admin: user, campaignTokenAccount: null })
+        .rpc();
+      assert.fail("close should require the token account");
+    } catch (err) {
+      assert.include(err.toString(), "TokenAccountRequired");
+    }
+
+    const adminTokenAccount = await createTokenAccount(user, mint);
+    await program.methods
+      .withdrawSpl(name, new anchor.BN(10))
+      .accountsPartial({
+        campaign,
+        mint,
+        campaignTokenAccount,
+        adminTokenAccount,
+        feeCollectorTokenAccount: adminTokenAccount,
+        admin: user,
+      })
+      .rpc();
+    await program.methods
+      .close(name)
+      .accountsPartial({ campaign, admin: user, campaignTokenAccount })
+      .rpc();
+    assert.isNull(await provider.connection.getAccountInfo(campaignTokenAccount));
+    assert.isNull(await provider.connection.getAccountInfo(campaign));
+  });
 });